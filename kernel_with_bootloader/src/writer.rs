@@ -7,8 +7,10 @@ use core::{
     sync::atomic::{AtomicBool, Ordering},
 };
 
+use alloc::collections::VecDeque;
 use alloc::vec;
 use alloc::vec::Vec;
+use bitflags::bitflags;
 use bootloader_api::info::{FrameBufferInfo, PixelFormat};
 use constants::font_constants;
 use constants::font_constants::{BACKUP_CHAR, CHAR_RASTER_HEIGHT, CHAR_RASTER_WIDTH, FONT_WEIGHT};
@@ -32,13 +34,113 @@ const BORDER_PADDING: usize = 5;
 
 /*
 Overview of the additions in this file:
-- Insert mode: keep a shadow grid (Vec<Option<char>>) of what's on screen so we can shift text
-  right/left instead of painting over pixels. Lines redraw from this buffer to avoid artifacts.
+- Insert mode: keep a shadow grid (Vec<Cell>) of what's on screen so we can shift text right/left
+  instead of painting over pixels. Lines redraw from this buffer to avoid artifacts.
 - Visible blinking cursor: draw/erase/toggle functions paint a caret over the current cell; the
   timer interrupt flips the caret state.
 - Helpers: coordinate/index helpers and redraw logic to keep cursor and buffer aligned.
+- A small VT100/ANSI escape parser so programs that emit terminal control codes (cursor motion,
+  screen/line clears, SGR attributes) render correctly instead of printing the raw escape bytes.
+- Scrollback: rows pushed off the top of the grid are kept in a ring buffer instead of being
+  discarded, and `scroll_up`/`scroll_down` let callers view them before snapping back to live.
+- Soft-wrap tracking: `wrapped_rows` records which rows were split by hitting the column limit
+  (as opposed to an explicit `\n`), so `resize` can rejoin and re-wrap logical lines instead of
+  discarding text when the geometry changes.
 */
 
+/// State of the CSI (`ESC[...]`) escape-sequence parser fed by [`FrameBufferWriter::write_char`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AnsiState {
+    /// No escape sequence in progress; characters are printed normally.
+    Ground,
+    /// Just saw `ESC`; waiting to see whether this is a CSI sequence.
+    Escape,
+    /// Inside `ESC[...]`; collecting numeric parameters until a final byte arrives.
+    Csi,
+}
+
+/// Maximum number of numeric parameters collected for a single CSI sequence.
+///
+/// Fixed-size so a stray escape sequence never triggers an allocation from `write_str`.
+const MAX_CSI_PARAMS: usize = 16;
+
+/// An RGB color, as set by SGR (`ESC[...m`) codes.
+type Rgb = (u8, u8, u8);
+
+bitflags! {
+    /// Per-cell text attributes toggled via SGR (`ESC[...m`) codes.
+    #[derive(Clone, Copy, PartialEq, Eq, Default)]
+    struct CellAttrs: u8 {
+        const BOLD = 0b0000_0001;
+        const UNDERLINE = 0b0000_0010;
+        const REVERSE = 0b0000_0100;
+    }
+}
+
+/// The 8 classic ANSI colors, indexed by the `0`-`7` in SGR codes `30`-`37`/`40`-`47`.
+const ANSI_COLORS: [Rgb; 8] = [
+    (0x00, 0x00, 0x00), // black
+    (0xaa, 0x00, 0x00), // red
+    (0x00, 0xaa, 0x00), // green
+    (0xaa, 0x55, 0x00), // yellow
+    (0x00, 0x00, 0xaa), // blue
+    (0xaa, 0x00, 0xaa), // magenta
+    (0x00, 0xaa, 0xaa), // cyan
+    (0xaa, 0xaa, 0xaa), // white
+];
+
+const DEFAULT_FG: Rgb = ANSI_COLORS[7];
+const DEFAULT_BG: Rgb = ANSI_COLORS[0];
+
+/// Number of off-screen rows kept in the scrollback ring buffer.
+const MAX_SCROLLBACK_ROWS: usize = 1000;
+
+/// Width, in pixels, of the vertical bar drawn for [`CursorStyle::Beam`].
+const CURSOR_BEAM_WIDTH: usize = 2;
+
+/// Height, in pixels, of the bar drawn along the baseline for [`CursorStyle::Underline`].
+const CURSOR_UNDERLINE_HEIGHT: usize = 2;
+
+/// Shape the caret is painted in, settable via [`FrameBufferWriter::set_cursor_style`] or the
+/// DECSCUSR escape (`ESC[{n} q`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CursorStyle {
+    /// A solid filled cell.
+    Block,
+    /// A thin vertical bar at the left edge of the cell.
+    Beam,
+    /// A bar along the bottom of the cell.
+    Underline,
+    /// An outline of the cell, leaving the glyph underneath visible.
+    HollowBlock,
+}
+
+/// One cell of the shadow text grid: the glyph plus the pen state it was written with.
+///
+/// A double-width char (see [`char_width`]) occupies two consecutive cells: a leading cell
+/// holding the glyph, followed by a `spacer` cell that reserves the second column so the
+/// cursor and grid indexing never split the char in two.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    ch: Option<char>,
+    fg: Rgb,
+    bg: Rgb,
+    attrs: CellAttrs,
+    spacer: bool,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: None,
+            fg: DEFAULT_FG,
+            bg: DEFAULT_BG,
+            attrs: CellAttrs::empty(),
+            spacer: false,
+        }
+    }
+}
+
 /// Returns the raster of the given char or the raster of [`font_constants::BACKUP_CHAR`].
 pub fn get_char_raster(c: char) -> RasterizedChar {
     fn get(c: char) -> Option<RasterizedChar> {
@@ -47,6 +149,32 @@ pub fn get_char_raster(c: char) -> RasterizedChar {
     get(c).unwrap_or_else(|| get(BACKUP_CHAR).expect("Should get raster of backup char."))
 }
 
+/// Columns a char occupies in the grid: 2 for CJK/fullwidth ranges and the common emoji
+/// blocks, 1 otherwise. Mirrors how terminal renderers classify double-width glyphs.
+fn char_width(c: char) -> usize {
+    let cp = c as u32;
+    let wide = matches!(cp,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1FAFF
+        | 0x20000..=0x3FFFD
+    );
+    if wide {
+        2
+    } else {
+        1
+    }
+}
+
 /// Allows logging text to a pixel-based framebuffer.
 pub struct FrameBufferWriter {
     framebuffer: &'static mut [u8],
@@ -56,7 +184,23 @@ pub struct FrameBufferWriter {
     // Shadow text grid so we can shift characters for insert/backspace instead of overwriting pixels.
     cols: usize,
     rows: usize,
-    buffer: Vec<Option<char>>,
+    buffer: Vec<Cell>,
+    // VT100/ANSI escape parser state.
+    ansi_state: AnsiState,
+    csi_params: [u16; MAX_CSI_PARAMS],
+    csi_params_len: usize,
+    // Current pen attributes, set via SGR and stamped onto newly written cells.
+    cur_fg: Rgb,
+    cur_bg: Rgb,
+    cur_attrs: CellAttrs,
+    cursor_style: CursorStyle,
+    // Scrollback: rows pushed off the top of the visible grid, oldest first.
+    scrollback: VecDeque<Vec<Cell>>,
+    // How many rows back from the live bottom the view is currently scrolled.
+    scroll_offset: usize,
+    // Parallels `buffer` one entry per row: true if the row was split by a column-limit wrap
+    // (as opposed to an explicit `\n`), so `resize` can rejoin it into its logical line.
+    wrapped_rows: Vec<bool>,
 }
 
 lazy_static! {
@@ -78,6 +222,16 @@ impl FrameBufferWriter {
             cols: 0,
             rows: 0,
             buffer: Vec::new(),
+            ansi_state: AnsiState::Ground,
+            csi_params: [0; MAX_CSI_PARAMS],
+            csi_params_len: 0,
+            cur_fg: DEFAULT_FG,
+            cur_bg: DEFAULT_BG,
+            cur_attrs: CellAttrs::empty(),
+            cursor_style: CursorStyle::Block,
+            scrollback: VecDeque::new(),
+            scroll_offset: 0,
+            wrapped_rows: Vec::new(),
         };
 
         logger.clear();
@@ -106,14 +260,114 @@ impl FrameBufferWriter {
         let mut row = self.current_row();
         row += 1;
         if row >= self.rows {
-            self.clear();
-            row = 0;
+            self.scroll_line_up();
+            row = self.rows - 1;
         }
         self.x_pos = BORDER_PADDING;
         self.y_pos = BORDER_PADDING + row * line_height;
         self.draw_cursor();
     }
 
+    /// Pushes the top visible row into scrollback and shifts the remaining rows up by one,
+    /// repainting the framebuffer once. Used when `newline` overflows the bottom of the grid,
+    /// replacing the old destructive `clear()`.
+    fn scroll_line_up(&mut self) {
+        let top_row: Vec<Cell> = (0..self.cols).map(|col| self.buffer[self.index(0, col)]).collect();
+        if self.scrollback.len() >= MAX_SCROLLBACK_ROWS {
+            self.scrollback.pop_front();
+        }
+        self.scrollback.push_back(top_row);
+
+        for row in 1..self.rows {
+            for col in 0..self.cols {
+                let cell = self.buffer[self.index(row, col)];
+                self.buffer[self.index(row - 1, col)] = cell;
+            }
+            self.wrapped_rows[row - 1] = self.wrapped_rows[row];
+        }
+        let last_row = self.rows - 1;
+        for col in 0..self.cols {
+            self.buffer[self.index(last_row, col)] = Cell::default();
+        }
+        self.wrapped_rows[last_row] = false;
+
+        for row in 0..self.rows {
+            self.redraw_line(row);
+        }
+    }
+
+    /// Scrolls the view `n` rows further back into scrollback history, repainting the
+    /// framebuffer from the combined history + live buffer.
+    pub fn scroll_up(&mut self, n: usize) {
+        self.scroll_offset = (self.scroll_offset + n).min(self.scrollback.len());
+        self.render_scrolled();
+    }
+
+    /// Scrolls the view `n` rows back towards the live bottom.
+    pub fn scroll_down(&mut self, n: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(n);
+        self.render_scrolled();
+    }
+
+    /// Resets the view to the live bottom, repainting if it had been scrolled back.
+    fn snap_to_bottom(&mut self) {
+        if self.scroll_offset != 0 {
+            self.scroll_offset = 0;
+            for row in 0..self.rows {
+                self.redraw_line(row);
+            }
+        }
+    }
+
+    /// Returns the cell at `timeline_row` (scrollback first, then the live buffer), where
+    /// `history_len` is `self.scrollback.len()` at the time of the call.
+    fn timeline_cell(&self, timeline_row: usize, history_len: usize, col: usize) -> Cell {
+        if timeline_row < history_len {
+            self.scrollback[timeline_row][col]
+        } else {
+            self.buffer[self.index(timeline_row - history_len, col)]
+        }
+    }
+
+    /// Repaints every screen row from `scroll_offset` rows back in the combined
+    /// scrollback+live timeline, without touching the live `buffer`.
+    fn render_scrolled(&mut self) {
+        let history_len = self.scrollback.len();
+        let start = history_len.saturating_sub(self.scroll_offset);
+        for screen_row in 0..self.rows {
+            let timeline_row = start + screen_row;
+            let y_start = BORDER_PADDING + screen_row * (CHAR_RASTER_HEIGHT.val() + LINE_SPACING);
+            let mut col = 0;
+            while col < self.cols {
+                let cell = self.timeline_cell(timeline_row, history_len, col);
+                if cell.spacer {
+                    col += 1;
+                    continue;
+                }
+                let width = if col + 1 < self.cols
+                    && self.timeline_cell(timeline_row, history_len, col + 1).spacer
+                {
+                    2
+                } else {
+                    1
+                };
+                let x_start = BORDER_PADDING + col * (CHAR_RASTER_WIDTH + LETTER_SPACING);
+                self.draw_cell(cell, x_start, y_start, width);
+                col += width;
+            }
+        }
+    }
+
+    /// Advances to the next line because the current one filled up, marking it as soft-wrapped
+    /// (as opposed to ending on an explicit `\n`) so `resize` can later rejoin it.
+    fn wrap_row(&mut self) {
+        let row = self.current_row();
+        if let Some(flag) = self.wrapped_rows.get_mut(row) {
+            *flag = true;
+        }
+        self.newline();
+    }
+
     fn carriage_return(&mut self) {
         self.erase_cursor();
         self.x_pos = BORDER_PADDING;
@@ -144,21 +398,40 @@ impl FrameBufferWriter {
         self.draw_cursor();
     }
 
-    pub fn cursor_left(&mut self) {
-        self.erase_cursor();
+    /// Moves the pixel cursor one cell to the left, without any spacer awareness. Returns
+    /// false if already at the top-left corner (and leaves the caret erased, matching the
+    /// existing quirk of not redrawing it when there's nowhere to go).
+    fn step_left(&mut self) -> bool {
         if self.x_pos > BORDER_PADDING {
             // Move the cursor back by one character width
             self.x_pos -= font_constants::CHAR_RASTER_WIDTH;
+            true
+        } else if self.y_pos
+            >= font_constants::CHAR_RASTER_HEIGHT.val() + LINE_SPACING + BORDER_PADDING
+        {
+            self.x_pos = self.width() - (font_constants::CHAR_RASTER_WIDTH + LETTER_SPACING);
+            self.y_pos -= font_constants::CHAR_RASTER_HEIGHT.val() + LINE_SPACING;
+            true
         } else {
-            if self.y_pos
-                >= font_constants::CHAR_RASTER_HEIGHT.val() + LINE_SPACING + BORDER_PADDING
-            {
-                self.x_pos = self.width() - (font_constants::CHAR_RASTER_WIDTH + LETTER_SPACING);
-                self.y_pos -= font_constants::CHAR_RASTER_HEIGHT.val() + LINE_SPACING;
-            } else {
-                // Already at the top-left position, can't go back further
-                return;
-            }
+            // Already at the top-left position, can't go back further
+            false
+        }
+    }
+
+    /// Whether the cell currently under the cursor is the trailing half of a wide char.
+    fn on_spacer(&self) -> bool {
+        let row = self.current_row();
+        let col = self.current_col();
+        row < self.rows && col < self.cols && self.buffer[self.index(row, col)].spacer
+    }
+
+    pub fn cursor_left(&mut self) {
+        self.erase_cursor();
+        if !self.step_left() {
+            return;
+        }
+        if self.on_spacer() {
+            self.step_left();
         }
         self.draw_cursor();
     }
@@ -173,6 +446,14 @@ impl FrameBufferWriter {
             self.newline();
             return;
         }
+        if self.on_spacer() {
+            if self.x_pos + font_constants::CHAR_RASTER_WIDTH + LETTER_SPACING < self.width() {
+                self.x_pos += font_constants::CHAR_RASTER_WIDTH + LETTER_SPACING;
+            } else {
+                self.newline();
+                return;
+            }
+        }
         self.draw_cursor();
     }
 
@@ -208,28 +489,34 @@ impl FrameBufferWriter {
             return;
         }
         self.erase_cursor();
-        if self.x_pos > BORDER_PADDING {
-            // Move the cursor back by one character width
-            self.x_pos -= font_constants::CHAR_RASTER_WIDTH;
-        } else {
-            if self.y_pos
-                >= font_constants::CHAR_RASTER_HEIGHT.val() + LINE_SPACING + BORDER_PADDING
-            {
-                self.x_pos = self.width() - (font_constants::CHAR_RASTER_WIDTH + LETTER_SPACING);
-                self.y_pos -= font_constants::CHAR_RASTER_HEIGHT.val() + LINE_SPACING;
-            } else {
-                // Already at the top-left position, can't go back further
-                return;
-            }
+        if !self.step_left() {
+            return;
+        }
+        if self.on_spacer() {
+            self.step_left();
         }
         let target_col = self.current_col();
         let target_row = self.current_row();
+        let width = self.wide_span(target_row, target_col);
         let start = self.index(target_row, target_col);
         let end = self.index(target_row, self.cols - 1);
-        for i in start..end {
-            self.buffer[i] = self.buffer[i + 1];
+        // In a narrow grid a double-width char can reach to (or past) the last column, leaving
+        // no cells after it to shift left; `end - width` would then underflow.
+        match end.checked_sub(width).filter(|&shift_end| shift_end >= start) {
+            Some(shift_end) => {
+                for i in start..=shift_end {
+                    self.buffer[i] = self.buffer[i + width];
+                }
+                for i in (shift_end + 1)..=end {
+                    self.buffer[i] = Cell::default();
+                }
+            }
+            None => {
+                for i in start..=end {
+                    self.buffer[i] = Cell::default();
+                }
+            }
         }
-        self.buffer[end] = None;
         self.redraw_line(target_row);
         self.draw_cursor();
     }
@@ -244,7 +531,8 @@ impl FrameBufferWriter {
             self.cols = 1;
             self.rows = 1;
         }
-        self.buffer = vec![None; self.cols * self.rows];
+        self.buffer = vec![Cell::default(); self.cols * self.rows];
+        self.wrapped_rows = vec![false; self.rows];
         self.x_pos = BORDER_PADDING;
         self.y_pos = BORDER_PADDING;
         self.framebuffer.fill(0);
@@ -252,9 +540,128 @@ impl FrameBufferWriter {
         self.draw_cursor();
     }
 
+    /// Reflows the grid onto a new geometry: consecutive rows joined by a soft wrap are
+    /// rejoined into their logical line, each logical line is re-wrapped to `new_cols`, and
+    /// rows that no longer fit in `new_rows` are pushed into scrollback — preserving text
+    /// across a resize instead of discarding it the way `clear()` would.
+    pub fn resize(&mut self, new_cols: usize, new_rows: usize) {
+        if new_cols == 0 || new_rows == 0 || (new_cols == self.cols && new_rows == self.rows) {
+            return;
+        }
+
+        // Existing scrollback rows were pushed at the old `self.cols` width; pad or truncate
+        // them to `new_cols` too, or `timeline_cell`/`render_scrolled` will index past the end
+        // of a narrower row once `self.cols` grows.
+        for row in self.scrollback.iter_mut() {
+            row.resize(new_cols, Cell::default());
+        }
+
+        let mut logical_lines: Vec<Vec<Cell>> = Vec::new();
+        let mut current: Vec<Cell> = Vec::new();
+        for row in 0..self.rows {
+            if self.wrapped_rows.get(row).copied().unwrap_or(false) {
+                // The writer wraps before filling the last column (see `current_col() + 1 >=
+                // self.cols` in `write_char_ground`), so a soft-wrapped row leaves that column
+                // as `Cell::default()` unless a double-width char's spacer landed there. Drop a
+                // blank (non-spacer) last column so rejoining doesn't splice a spurious blank
+                // char into the middle of the logical line.
+                let mut end = self.cols;
+                if end > 0
+                    && self.buffer[self.index(row, end - 1)].ch.is_none()
+                    && !self.buffer[self.index(row, end - 1)].spacer
+                {
+                    end -= 1;
+                }
+                for col in 0..end {
+                    current.push(self.buffer[self.index(row, col)]);
+                }
+            } else {
+                let mut end = self.cols;
+                while end > 0 && self.buffer[self.index(row, end - 1)].ch.is_none() {
+                    end -= 1;
+                }
+                for col in 0..end {
+                    current.push(self.buffer[self.index(row, col)]);
+                }
+                logical_lines.push(core::mem::take(&mut current));
+            }
+        }
+        if !current.is_empty() {
+            logical_lines.push(current);
+        }
+
+        // Re-wrap each logical line to the new width.
+        let mut new_rows_data: Vec<(Vec<Cell>, bool)> = Vec::new();
+        for line in logical_lines {
+            if line.is_empty() {
+                new_rows_data.push((Vec::new(), false));
+                continue;
+            }
+            let mut start = 0;
+            while start < line.len() {
+                let end = (start + new_cols).min(line.len());
+                new_rows_data.push((line[start..end].to_vec(), end < line.len()));
+                start = end;
+            }
+        }
+
+        // Anything that no longer fits on screen goes to scrollback, oldest first.
+        if new_rows_data.len() > new_rows {
+            let overflow = new_rows_data.len() - new_rows;
+            for (chunk, _) in new_rows_data.drain(..overflow) {
+                let mut row = chunk;
+                row.resize(new_cols, Cell::default());
+                if self.scrollback.len() >= MAX_SCROLLBACK_ROWS {
+                    self.scrollback.pop_front();
+                }
+                self.scrollback.push_back(row);
+            }
+        }
+
+        self.cols = new_cols;
+        self.rows = new_rows;
+        self.buffer = vec![Cell::default(); new_cols * new_rows];
+        self.wrapped_rows = vec![false; new_rows];
+        for (row, (chunk, wraps)) in new_rows_data.into_iter().enumerate() {
+            for (col, cell) in chunk.into_iter().enumerate() {
+                self.buffer[row * new_cols + col] = cell;
+            }
+            self.wrapped_rows[row] = wraps;
+        }
+
+        self.x_pos = BORDER_PADDING;
+        self.y_pos = BORDER_PADDING;
+        self.scroll_offset = 0;
+        self.framebuffer.fill(0);
+        for row in 0..self.rows {
+            self.redraw_line(row);
+        }
+        self.draw_cursor();
+    }
+
+    /// Whether `row` was split from the next one by a column-limit wrap rather than an
+    /// explicit newline, letting reflow logic (or tests) reconstruct soft-wrap state.
+    pub fn row_wrapped(&self, row: usize) -> bool {
+        self.wrapped_rows.get(row).copied().unwrap_or(false)
+    }
+
     /// Writes a single char to the framebuffer. Takes care of special control characters, such as
-    /// newlines and carriage returns.
+    /// newlines and carriage returns, and feeds everything through the VT100/ANSI escape parser
+    /// so CSI sequences (cursor motion, clears, SGR) are recognized instead of printed literally.
     pub fn write_char(&mut self, c: char) {
+        self.snap_to_bottom();
+        match self.ansi_state {
+            AnsiState::Ground => self.write_char_ground(c),
+            AnsiState::Escape => self.write_char_escape(c),
+            AnsiState::Csi => self.write_char_csi(c),
+        }
+    }
+
+    fn write_char_ground(&mut self, c: char) {
+        if c == '\u{1b}' {
+            self.ansi_state = AnsiState::Escape;
+            return;
+        }
         self.erase_cursor();
         match c {
             '\t' => self.tab(),
@@ -262,7 +669,7 @@ impl FrameBufferWriter {
             '\r' => self.carriage_return(),
             c => {
                 if self.current_col() + 1 >= self.cols {
-                    self.newline();
+                    self.wrap_row();
                 }
                 if self.current_row() >= self.rows {
                     self.clear();
@@ -273,6 +680,198 @@ impl FrameBufferWriter {
         self.draw_cursor();
     }
 
+    fn write_char_escape(&mut self, c: char) {
+        match c {
+            '[' => {
+                self.csi_params = [0; MAX_CSI_PARAMS];
+                self.csi_params_len = 0;
+                self.ansi_state = AnsiState::Csi;
+            }
+            // Not a CSI sequence; we don't implement other escape kinds yet, so bail back to
+            // ground rather than printing the escape byte or the char that follows it.
+            _ => self.ansi_state = AnsiState::Ground,
+        }
+    }
+
+    fn write_char_csi(&mut self, c: char) {
+        match c {
+            '0'..='9' => {
+                if self.csi_params_len == 0 {
+                    self.csi_params_len = 1;
+                }
+                if let Some(param) = self.csi_params.get_mut(self.csi_params_len - 1) {
+                    *param = param
+                        .saturating_mul(10)
+                        .saturating_add(c as u16 - '0' as u16);
+                }
+            }
+            ';' => {
+                // A leading `;` (e.g. `ESC[;5H`) means param 0 was left empty; reserve it at
+                // its default before advancing, or the next digit would overwrite it instead of
+                // starting param 1.
+                if self.csi_params_len == 0 {
+                    self.csi_params_len = 1;
+                }
+                if self.csi_params_len < MAX_CSI_PARAMS {
+                    self.csi_params_len += 1;
+                } else {
+                    self.csi_params_len = MAX_CSI_PARAMS;
+                }
+            }
+            // Intermediate byte (e.g. the space in DECSCUSR's `ESC[{n} q`): keep collecting.
+            ' ' => {}
+            final_byte @ ('A'..='Z' | 'a'..='z' | '@' | '`') => {
+                self.dispatch_csi(final_byte);
+                self.ansi_state = AnsiState::Ground;
+            }
+            // Any other byte (e.g. the private-mode `?` of `ESC[?25l`) abandons the sequence:
+            // that byte is dropped and the parser returns to ground, so the bytes after it are
+            // printed as ordinary text.
+            _ => self.ansi_state = AnsiState::Ground,
+        }
+    }
+
+    /// Returns the CSI parameter at `index`, or `default` if it was omitted or left at zero
+    /// (per ANSI convention, an explicit `0` and an omitted parameter both mean "default").
+    fn csi_param(&self, index: usize, default: u16) -> u16 {
+        match self.csi_params.get(index).copied() {
+            Some(0) | None => default,
+            Some(value) => value,
+        }
+    }
+
+    fn dispatch_csi(&mut self, final_byte: char) {
+        match final_byte {
+            'A' => {
+                for _ in 0..self.csi_param(0, 1) {
+                    self.cursor_up();
+                }
+            }
+            'B' => {
+                for _ in 0..self.csi_param(0, 1) {
+                    self.cursor_down();
+                }
+            }
+            'C' => {
+                for _ in 0..self.csi_param(0, 1) {
+                    self.cursor_right();
+                }
+            }
+            'D' => {
+                for _ in 0..self.csi_param(0, 1) {
+                    self.cursor_left();
+                }
+            }
+            'H' | 'f' => {
+                // Clamp to the grid: unlike cursor_up/down/left/right (which clamp themselves),
+                // a row/col past `rows`/`cols` (e.g. the common `ESC[999;999H` "go to
+                // bottom-right" idiom emitted by vim/less/etc.) would otherwise position the
+                // pixel cursor outside the framebuffer and panic on the next draw.
+                let row = (self.csi_param(0, 1) as usize).clamp(1, self.rows);
+                let col = (self.csi_param(1, 1) as usize).clamp(1, self.cols);
+                let char_w = CHAR_RASTER_WIDTH + LETTER_SPACING;
+                let char_h = CHAR_RASTER_HEIGHT.val() + LINE_SPACING;
+                self.set_y_pos(BORDER_PADDING + (row - 1) * char_h);
+                self.set_x_pos(BORDER_PADDING + (col - 1) * char_w);
+            }
+            'J' => {
+                if self.csi_param(0, 0) == 2 {
+                    self.clear();
+                }
+            }
+            'K' => self.erase_in_line(self.csi_param(0, 0)),
+            'm' => self.dispatch_sgr(),
+            'q' => self.dispatch_decscusr(self.csi_param(0, 1)),
+            // Unrecognized final byte: ignore the whole sequence rather than printing it.
+            _ => {}
+        }
+    }
+
+    /// Applies a DECSCUSR (`ESC[{n} q`) cursor-shape request. `n` follows xterm's convention:
+    /// 0/1/2 block, 3/4 underline, 5/6 beam (blink vs. steady is not modeled, so both map the
+    /// same way); any other value is left unrecognized.
+    fn dispatch_decscusr(&mut self, n: u16) {
+        let style = match n {
+            0 | 1 | 2 => CursorStyle::Block,
+            3 | 4 => CursorStyle::Underline,
+            5 | 6 => CursorStyle::Beam,
+            _ => return,
+        };
+        self.set_cursor_style(style);
+    }
+
+    /// Erases part of the current line in the shadow buffer, mirroring the `EL` (`ESC[K`)
+    /// control: `0` cursor-to-end, `1` start-to-cursor, `2` the entire line.
+    fn erase_in_line(&mut self, mode: u16) {
+        let row = self.current_row();
+        if row >= self.rows {
+            return;
+        }
+        let col = self.current_col().min(self.cols.saturating_sub(1));
+        let (start, end) = match mode {
+            1 => (0, col),
+            2 => (0, self.cols.saturating_sub(1)),
+            _ => (col, self.cols.saturating_sub(1)),
+        };
+        for c in start..=end {
+            self.buffer[self.index(row, c)] = Cell::default();
+        }
+        self.redraw_line(row);
+    }
+
+    /// Applies an SGR (`ESC[...m`) sequence to the current pen state: `0` resets, `1`/`4`/`7`
+    /// set bold/underline/reverse (`22`/`24`/`27` clear them), `30`-`37`/`40`-`47` pick one of
+    /// the 8 ANSI colors, `38;2;r;g;b`/`48;2;r;g;b` set truecolor, `39`/`49` reset fg/bg.
+    fn dispatch_sgr(&mut self) {
+        if self.csi_params_len == 0 {
+            self.reset_attrs();
+            return;
+        }
+        let mut i = 0;
+        while i < self.csi_params_len {
+            match self.csi_params[i] {
+                0 => self.reset_attrs(),
+                1 => self.cur_attrs.insert(CellAttrs::BOLD),
+                4 => self.cur_attrs.insert(CellAttrs::UNDERLINE),
+                7 => self.cur_attrs.insert(CellAttrs::REVERSE),
+                22 => self.cur_attrs.remove(CellAttrs::BOLD),
+                24 => self.cur_attrs.remove(CellAttrs::UNDERLINE),
+                27 => self.cur_attrs.remove(CellAttrs::REVERSE),
+                code @ 30..=37 => self.cur_fg = ANSI_COLORS[(code - 30) as usize],
+                code @ 40..=47 => self.cur_bg = ANSI_COLORS[(code - 40) as usize],
+                39 => self.cur_fg = DEFAULT_FG,
+                49 => self.cur_bg = DEFAULT_BG,
+                code @ (38 | 48) => {
+                    // Truecolor: `38;2;r;g;b` (fg) or `48;2;r;g;b` (bg); any other mode byte
+                    // (e.g. the 256-color `5;n` form) is left unsupported and skipped.
+                    if self.csi_params.get(i + 1).copied() == Some(2) {
+                        let color = (
+                            self.csi_params.get(i + 2).copied().unwrap_or(0) as u8,
+                            self.csi_params.get(i + 3).copied().unwrap_or(0) as u8,
+                            self.csi_params.get(i + 4).copied().unwrap_or(0) as u8,
+                        );
+                        if code == 38 {
+                            self.cur_fg = color;
+                        } else {
+                            self.cur_bg = color;
+                        }
+                        i += 4;
+                    } else if self.csi_params.get(i + 1).copied() == Some(5) {
+                        i += 2;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    fn reset_attrs(&mut self) {
+        self.cur_fg = DEFAULT_FG;
+        self.cur_bg = DEFAULT_BG;
+        self.cur_attrs = CellAttrs::empty();
+    }
+
     /// Prints a rendered char into the framebuffer.
     /// Updates `self.x_pos`.
     #[allow(dead_code)]
@@ -306,6 +905,31 @@ impl FrameBufferWriter {
         let _ = unsafe { ptr::read_volatile(&self.framebuffer[byte_offset]) };
     }
 
+    /// Like [`Self::write_pixel`], but for a full RGB color instead of a grayscale intensity.
+    fn write_pixel_rgb(&mut self, x: usize, y: usize, color: Rgb) {
+        let pixel_offset = (y * self.info.stride) + x;
+        let (r, g, b) = color;
+        let bytes = match self.info.pixel_format {
+            PixelFormat::Rgb => [r, g, b, 0],
+            PixelFormat::Bgr => [b, g, r, 0],
+            PixelFormat::U8 => {
+                let luma = (r as u32 + g as u32 + b as u32) / 3;
+                [if luma > 128 { 0xf } else { 0 }, 0, 0, 0]
+            }
+            other => {
+                // set a supported (but invalid) pixel format before panicking to avoid a double
+                // panic; it might not be readable though
+                self.info.pixel_format = PixelFormat::Rgb;
+                panic!("pixel format {:?} not supported in logger", other)
+            }
+        };
+        let bytes_per_pixel = self.info.bytes_per_pixel;
+        let byte_offset = pixel_offset * bytes_per_pixel;
+        self.framebuffer[byte_offset..(byte_offset + bytes_per_pixel)]
+            .copy_from_slice(&bytes[..bytes_per_pixel]);
+        let _ = unsafe { ptr::read_volatile(&self.framebuffer[byte_offset]) };
+    }
+
     fn current_col(&self) -> usize {
         (self.x_pos.saturating_sub(BORDER_PADDING)) / (CHAR_RASTER_WIDTH + LETTER_SPACING)
     }
@@ -319,25 +943,50 @@ impl FrameBufferWriter {
     }
 
     fn insert_into_buffer(&mut self, c: char) {
-        let row = self.current_row();
-        let col = self.current_col();
+        let width = char_width(c);
+        let mut row = self.current_row();
+        let mut col = self.current_col();
         if row >= self.rows || col >= self.cols {
             return;
         }
-        // shift right within line
+        if width == 2 && col + 1 >= self.cols {
+            // Only one column left on this line: wrap rather than split the glyph.
+            self.wrap_row();
+            row = self.current_row();
+            col = self.current_col();
+            if row >= self.rows || col >= self.cols {
+                return;
+            }
+        }
+        // shift right within line to make room for `width` columns
         let start = self.index(row, col);
         let end = self.index(row, self.cols - 1);
-        for i in (start + 1..=end).rev() {
-            self.buffer[i] = self.buffer[i - 1];
+        for i in ((start + width)..=end).rev() {
+            self.buffer[i] = self.buffer[i - width];
+        }
+        self.buffer[start] = Cell {
+            ch: Some(c),
+            fg: self.cur_fg,
+            bg: self.cur_bg,
+            attrs: self.cur_attrs,
+            spacer: false,
+        };
+        if width == 2 {
+            self.buffer[start + 1] = Cell {
+                ch: None,
+                fg: self.cur_fg,
+                bg: self.cur_bg,
+                attrs: self.cur_attrs,
+                spacer: true,
+            };
         }
-        self.buffer[start] = Some(c);
         self.redraw_line(row);
-        // move cursor one step right
-        let next_col = (col + 1).min(self.cols.saturating_sub(1));
+        // move cursor past the char we just inserted
+        let next_col = (col + width).min(self.cols.saturating_sub(1));
         self.x_pos = BORDER_PADDING + next_col * (CHAR_RASTER_WIDTH + LETTER_SPACING);
-        // if we were at the last column, wrap to next line start
-        if col + 1 >= self.cols {
-            self.newline();
+        // if we were at (or past) the last column, wrap to next line start
+        if col + width >= self.cols {
+            self.wrap_row();
         }
     }
 
@@ -345,33 +994,113 @@ impl FrameBufferWriter {
         if row >= self.rows {
             return;
         }
-        // clear line band
         let y_start = BORDER_PADDING + row * (CHAR_RASTER_HEIGHT.val() + LINE_SPACING);
-        for y in 0..CHAR_RASTER_HEIGHT.val() {
-            for col in 0..self.cols {
-                let x_start = BORDER_PADDING + col * (CHAR_RASTER_WIDTH + LETTER_SPACING);
-                for x in 0..CHAR_RASTER_WIDTH {
-                    self.write_pixel(x_start + x, y_start + y, 0);
-                }
+        let mut col = 0;
+        while col < self.cols {
+            let cell = self.buffer[self.index(row, col)];
+            if cell.spacer {
+                // Already painted as part of the preceding wide char's leading cell.
+                col += 1;
+                continue;
             }
+            let width = self.wide_span(row, col);
+            let x_start = BORDER_PADDING + col * (CHAR_RASTER_WIDTH + LETTER_SPACING);
+            self.draw_cell(cell, x_start, y_start, width);
+            col += width;
         }
-        // redraw chars
-        for col in 0..self.cols {
-            if let Some(ch) = self.buffer[self.index(row, col)] {
-                let x = BORDER_PADDING + col * (CHAR_RASTER_WIDTH + LETTER_SPACING);
-                let y = y_start;
-                self.draw_char_at(ch, x, y);
+    }
+
+    /// Returns 2 if the cell at `(row, col)` is followed by a spacer (i.e. it holds a
+    /// double-width char), 1 otherwise.
+    fn wide_span(&self, row: usize, col: usize) -> usize {
+        if col + 1 < self.cols && self.buffer[self.index(row, col + 1)].spacer {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Paints a single cell, `width` columns wide, at `(x, y)`: the glyph if present (centered
+    /// across the full width for double-width chars), otherwise its background color.
+    fn draw_cell(&mut self, cell: Cell, x: usize, y: usize, width: usize) {
+        match cell.ch {
+            Some(ch) => self.draw_char_at(ch, x, y, cell.fg, cell.bg, cell.attrs, width),
+            None => {
+                let bg = if cell.attrs.contains(CellAttrs::REVERSE) {
+                    cell.fg
+                } else {
+                    cell.bg
+                };
+                for dy in 0..CHAR_RASTER_HEIGHT.val() {
+                    for dx in 0..width * CHAR_RASTER_WIDTH {
+                        self.write_pixel_rgb(x + dx, y + dy, bg);
+                    }
+                }
             }
         }
     }
 
-    fn draw_char_at(&mut self, c: char, x: usize, y: usize) {
+    /// Draws `c` at `(x, y)` using `fg`/`bg` (reverse-video swaps them, bold brightens the
+    /// foreground), and underlines the cell along its baseline row if requested. `width` is the
+    /// number of columns (1 or 2) the char's cell spans; the glyph is centered within it, since
+    /// the bitmap font has no true double-width rasters for wide chars.
+    fn draw_char_at(&mut self, c: char, x: usize, y: usize, fg: Rgb, bg: Rgb, attrs: CellAttrs, width: usize) {
+        let (mut fg, bg) = if attrs.contains(CellAttrs::REVERSE) {
+            (bg, fg)
+        } else {
+            (fg, bg)
+        };
+        if attrs.contains(CellAttrs::BOLD) {
+            fg = Self::brighten(fg);
+        }
+        let cell_px_width = width * CHAR_RASTER_WIDTH;
+        for dy in 0..CHAR_RASTER_HEIGHT.val() {
+            for dx in 0..cell_px_width {
+                self.write_pixel_rgb(x + dx, y + dy, bg);
+            }
+        }
         let rendered = get_char_raster(c);
+        let x_offset = cell_px_width.saturating_sub(rendered.width()) / 2;
         for (dy, row) in rendered.raster().iter().enumerate() {
             for (dx, byte) in row.iter().enumerate() {
-                self.write_pixel(x + dx, y + dy, *byte);
+                self.write_pixel_rgb(x + x_offset + dx, y + dy, Self::blend(fg, bg, *byte));
             }
         }
+        if attrs.contains(CellAttrs::UNDERLINE) {
+            let under_y = y + CHAR_RASTER_HEIGHT.val().saturating_sub(1);
+            for dx in 0..cell_px_width {
+                self.write_pixel_rgb(x + dx, under_y, fg);
+            }
+        }
+    }
+
+    fn brighten(c: Rgb) -> Rgb {
+        (
+            c.0.saturating_add(64),
+            c.1.saturating_add(64),
+            c.2.saturating_add(64),
+        )
+    }
+
+    /// Mixes `fg` into `bg` weighted by the glyph's rasterized `intensity` (0 = pure `bg`,
+    /// 255 = pure `fg`), matching how `write_pixel`'s grayscale ramp treats raster bytes.
+    fn blend(fg: Rgb, bg: Rgb, intensity: u8) -> Rgb {
+        fn blend_channel(fg: u8, bg: u8, intensity: u8) -> u8 {
+            let (fg, bg, i) = (fg as u32, bg as u32, intensity as u32);
+            ((fg * i + bg * (255 - i)) / 255) as u8
+        }
+        (
+            blend_channel(fg.0, bg.0, intensity),
+            blend_channel(fg.1, bg.1, intensity),
+            blend_channel(fg.2, bg.2, intensity),
+        )
+    }
+
+    /// Sets the caret shape painted by [`Self::draw_cursor`].
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        self.erase_cursor();
+        self.cursor_style = style;
+        self.draw_cursor();
     }
 
     pub fn draw_cursor(&mut self) {
@@ -380,10 +1109,38 @@ impl FrameBufferWriter {
         }
         let x_start = self.x_pos;
         let y_start = self.y_pos;
-        // Fill the current cell so the caret is visible over any background glyph.
-        for y in 0..CHAR_RASTER_HEIGHT.val() {
-            for x in 0..CHAR_RASTER_WIDTH {
-                self.write_pixel(x_start + x, y_start + y, 200);
+        match self.cursor_style {
+            CursorStyle::Block => {
+                for y in 0..CHAR_RASTER_HEIGHT.val() {
+                    for x in 0..CHAR_RASTER_WIDTH {
+                        self.write_pixel(x_start + x, y_start + y, 200);
+                    }
+                }
+            }
+            CursorStyle::Beam => {
+                for y in 0..CHAR_RASTER_HEIGHT.val() {
+                    for x in 0..CURSOR_BEAM_WIDTH {
+                        self.write_pixel(x_start + x, y_start + y, 200);
+                    }
+                }
+            }
+            CursorStyle::Underline => {
+                let y0 = CHAR_RASTER_HEIGHT.val().saturating_sub(CURSOR_UNDERLINE_HEIGHT);
+                for y in y0..CHAR_RASTER_HEIGHT.val() {
+                    for x in 0..CHAR_RASTER_WIDTH {
+                        self.write_pixel(x_start + x, y_start + y, 200);
+                    }
+                }
+            }
+            CursorStyle::HollowBlock => {
+                for x in 0..CHAR_RASTER_WIDTH {
+                    self.write_pixel(x_start + x, y_start, 200);
+                    self.write_pixel(x_start + x, y_start + CHAR_RASTER_HEIGHT.val() - 1, 200);
+                }
+                for y in 0..CHAR_RASTER_HEIGHT.val() {
+                    self.write_pixel(x_start, y_start + y, 200);
+                    self.write_pixel(x_start + CHAR_RASTER_WIDTH - 1, y_start + y, 200);
+                }
             }
         }
     }
@@ -404,11 +1161,16 @@ impl FrameBufferWriter {
         let row = self.current_row();
         let col = self.current_col();
         if row < self.rows && col < self.cols {
-            if let Some(ch) = self.buffer[self.index(row, col)] {
+            let cell = self.buffer[self.index(row, col)];
+            if let Some(ch) = cell.ch {
                 self.draw_char_at(
                     ch,
                     BORDER_PADDING + col * (CHAR_RASTER_WIDTH + LETTER_SPACING),
                     BORDER_PADDING + row * (CHAR_RASTER_HEIGHT.val() + LINE_SPACING),
+                    cell.fg,
+                    cell.bg,
+                    cell.attrs,
+                    self.wide_span(row, col),
                 );
             }
         }
@@ -465,3 +1227,101 @@ pub fn _print(args: fmt::Arguments) {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+
+    /// Builds a `FrameBufferWriter` over a throwaway framebuffer, sized to `cols` x `rows`,
+    /// without going through `clear()` so tests can seed `buffer`/`wrapped_rows` directly.
+    fn test_writer(cols: usize, rows: usize) -> FrameBufferWriter {
+        // Sized generously enough to cover whatever (small) cols/rows the tests use, with the
+        // framebuffer itself sized to match so write_pixel's byte_offset never runs past the end.
+        const DIM: usize = 256;
+        const BYTES_PER_PIXEL: usize = 4;
+        let framebuffer: &'static mut [u8] =
+            Box::leak(vec![0u8; DIM * DIM * BYTES_PER_PIXEL].into_boxed_slice());
+        FrameBufferWriter {
+            framebuffer,
+            info: FrameBufferInfo {
+                byte_len: DIM * DIM * BYTES_PER_PIXEL,
+                width: DIM,
+                height: DIM,
+                pixel_format: PixelFormat::Rgb,
+                bytes_per_pixel: BYTES_PER_PIXEL,
+                stride: DIM,
+            },
+            x_pos: BORDER_PADDING,
+            y_pos: BORDER_PADDING,
+            cols,
+            rows,
+            buffer: vec![Cell::default(); cols * rows],
+            ansi_state: AnsiState::Ground,
+            csi_params: [0; MAX_CSI_PARAMS],
+            csi_params_len: 0,
+            cur_fg: DEFAULT_FG,
+            cur_bg: DEFAULT_BG,
+            cur_attrs: CellAttrs::empty(),
+            cursor_style: CursorStyle::Block,
+            scrollback: VecDeque::new(),
+            scroll_offset: 0,
+            wrapped_rows: vec![false; rows],
+        }
+    }
+
+    fn set_row(writer: &mut FrameBufferWriter, row: usize, text: &str) {
+        for (col, c) in text.chars().enumerate() {
+            writer.buffer[row * writer.cols + col].ch = Some(c);
+        }
+    }
+
+    fn row_text(writer: &FrameBufferWriter, row: usize) -> Vec<char> {
+        (0..writer.cols)
+            .filter_map(|col| writer.buffer[row * writer.cols + col].ch)
+            .collect()
+    }
+
+    #[test]
+    fn resize_wider_rejoins_soft_wrapped_line_without_spurious_blank() {
+        // "ABCD" on row 0 wraps into "EFGH" on row 1, matching how `write_char_ground` leaves
+        // the last column of a row wrapped after narrow chars as `Cell::default()`.
+        let mut writer = test_writer(5, 2);
+        set_row(&mut writer, 0, "ABCD");
+        writer.wrapped_rows[0] = true;
+        set_row(&mut writer, 1, "EFGH");
+
+        writer.resize(9, 2);
+
+        assert_eq!(row_text(&writer, 0), "ABCDEFGH".chars().collect::<Vec<_>>());
+        assert!(!writer.row_wrapped(0));
+        assert!(row_text(&writer, 1).is_empty());
+    }
+
+    #[test]
+    fn resize_narrower_rewraps_logical_line_and_marks_wrapped_rows() {
+        let mut writer = test_writer(9, 1);
+        set_row(&mut writer, 0, "ABCDEFGH");
+
+        writer.resize(5, 2);
+
+        assert_eq!(row_text(&writer, 0), "ABCDE".chars().collect::<Vec<_>>());
+        assert!(writer.row_wrapped(0));
+        assert_eq!(row_text(&writer, 1), "FGH".chars().collect::<Vec<_>>());
+        assert!(!writer.row_wrapped(1));
+    }
+
+    #[test]
+    fn resize_wider_then_scroll_up_does_not_panic_on_narrower_scrollback_rows() {
+        // A scrollback row pushed before the resize is still `self.cols` (5) cells wide; once
+        // `resize` grows `self.cols` to 9, `render_scrolled` indexes every row up to the new
+        // width and used to read past the end of this one.
+        let mut writer = test_writer(5, 1);
+        writer.scrollback.push_back(vec![Cell::default(); 5]);
+
+        writer.resize(9, 1);
+        writer.scroll_up(1);
+
+        assert_eq!(writer.scrollback[0].len(), 9);
+    }
+}